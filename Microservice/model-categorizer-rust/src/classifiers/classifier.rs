@@ -4,6 +4,8 @@ use crate::classifiers::{PatternMatcher, ContextResolver, DefaultModels,
     VERSION_10, VERSION_15, VERSION_20, VERSION_25, VERSION_30,
     VERSION_35, VERSION_37, VERSION_40, VERSION_45,
     CAP_VISION, CAP_EMBEDDING, CAP_CHAT};
+use std::cmp::Ordering;
+use std::collections::HashMap;
 
 /// Structured metadata for a model
 #[derive(Debug, Clone)]
@@ -27,7 +29,7 @@ pub struct ModelClassifier {
 }
 
 impl ModelClassifier {
-    /// Create a new classifier instance
+    /// Create a new classifier instance using the built-in tables
     pub fn new() -> Self {
         Self {
             patterns: PatternMatcher::new(),
@@ -36,16 +38,104 @@ impl ModelClassifier {
         }
     }
 
-    /// Classify a model ID into metadata
+    /// Create a classifier instance, loading context windows and default
+    /// models from `MODEL_CONFIG_FILE` when set and falling back to the
+    /// built-in tables otherwise
+    pub fn from_env() -> Result<Self, crate::config::ConfigError> {
+        Ok(Self {
+            patterns: PatternMatcher::new(),
+            context: ContextResolver::load()?,
+            defaults: DefaultModels::load()?,
+        })
+    }
+
+    /// Create a classifier instance with a caller-supplied pattern registry
+    /// (e.g. built via [`PatternMatcher::from_config`]), so downstream
+    /// services can register newly released models at startup without a
+    /// code change
+    pub fn with_patterns(patterns: PatternMatcher) -> Self {
+        Self {
+            patterns,
+            context: ContextResolver::new(),
+            defaults: DefaultModels::new(),
+        }
+    }
+
+    /// Classify a model ID into metadata, taking the top-scoring candidate
+    /// from [`Self::classify_model_candidates`]
     pub fn classify_model(&self, model_id: &str, provider_hint: &str) -> ModelMetadata {
+        self.classify_model_candidates(model_id, provider_hint)
+            .into_iter()
+            .next()
+            .map(|(meta, _)| meta)
+            .unwrap_or_else(|| self.build_metadata_for_provider(&model_id.to_lowercase(), PROVIDER_OTHER))
+    }
+
+    /// Scores every plausible provider interpretation of `model_id` instead
+    /// of committing to the first match, so a name like
+    /// `openrouter/anthropic/claude-3.5-sonnet` — which legitimately matches
+    /// both `openrouter`'s namespace prefix and `anthropic`'s substring
+    /// pattern — surfaces both candidates. Returned sorted by descending
+    /// confidence; `classify_model` just takes the top one. Image-generation
+    /// and embedding models are not scored: they return a single candidate
+    /// at confidence 1.0.
+    ///
+    /// Confidence weighting: an explicit `provider_hint` match scores
+    /// highest, a `prefix/rest` namespace match next, and a bare substring
+    /// pattern hit is weighted by how much of the model id that pattern
+    /// covers (the same specificity notion `PatternMatcher` ranks on).
+    pub fn classify_model_candidates(&self, model_id: &str, provider_hint: &str) -> Vec<(ModelMetadata, f32)> {
         let model_lower = model_id.to_lowercase();
         if self.is_image_generation_model(&model_lower) {
-            return self.create_image_generation_metadata(&model_lower, provider_hint);
+            return vec![(self.create_image_generation_metadata(&model_lower, provider_hint), 1.0)];
         }
         if self.is_embedding_model(&model_lower) {
-            return self.create_embedding_model_metadata(&model_lower, provider_hint);
+            return vec![(self.create_embedding_model_metadata(&model_lower, provider_hint), 1.0)];
+        }
+
+        let mut scores: HashMap<String, f32> = HashMap::new();
+
+        if !provider_hint.is_empty() {
+            let p = self.patterns.match_provider_by_name(&provider_hint.to_lowercase());
+            if !p.is_empty() {
+                Self::record_candidate(&mut scores, p, 1.0);
+            }
+        }
+
+        if let Some((pref, _)) = model_lower.split_once('/') {
+            let p = self.patterns.match_provider_by_name(pref);
+            if !p.is_empty() {
+                Self::record_candidate(&mut scores, p, 0.9);
+            }
+        }
+
+        for (provider, patterns) in &self.patterns.provider_patterns {
+            for pat in patterns {
+                if model_lower.contains(pat.as_str()) {
+                    let specificity = (pat.len() as f32 / model_lower.len().max(1) as f32).min(1.0);
+                    Self::record_candidate(&mut scores, provider.clone(), 0.4 + 0.3 * specificity);
+                }
+            }
+        }
+
+        if scores.is_empty() {
+            scores.insert(PROVIDER_OTHER.to_string(), 0.1);
+        }
+
+        let mut candidates: Vec<(ModelMetadata, f32)> = scores
+            .into_iter()
+            .map(|(provider, score)| (self.build_metadata_for_provider(&model_lower, &provider), score))
+            .collect();
+        candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(Ordering::Equal));
+        candidates
+    }
+
+    /// Keeps the higher of an existing score and a new one for `provider`
+    fn record_candidate(scores: &mut HashMap<String, f32>, provider: String, score: f32) {
+        let entry = scores.entry(provider).or_insert(0.0);
+        if score > *entry {
+            *entry = score;
         }
-        self.build_standard_model_metadata(&model_lower, provider_hint)
     }
 
     fn create_image_generation_metadata(&self, model_name: &str, provider_hint: &str) -> ModelMetadata {
@@ -78,17 +168,19 @@ impl ModelClassifier {
         }
     }
 
-    fn build_standard_model_metadata(&self, model_name: &str, provider_hint: &str) -> ModelMetadata {
-        let provider = self.determine_provider(model_name, provider_hint);
-        let series = self.determine_series(model_name, &provider);
-        let model_type = self.determine_type(model_name, &provider, &series);
-        let variant = self.determine_variant(model_name, &provider, &series);
+    /// Builds full metadata for `model_name` once its provider is already
+    /// decided, so [`Self::classify_model_candidates`] can build one
+    /// candidate per plausible provider without re-resolving it each time
+    fn build_metadata_for_provider(&self, model_name: &str, provider: &str) -> ModelMetadata {
+        let series = self.determine_series(model_name, provider);
+        let model_type = self.determine_type(model_name, provider, &series);
+        let variant = self.determine_variant(model_name, provider, &series);
         let context = self.context.get_context_size(model_name);
-        let caps = self.detect_capabilities(model_name, &provider, &series);
+        let caps = self.detect_capabilities(model_name, provider, &series);
         let is_multimodal = self.is_multimodal(model_name, &caps, &series);
         let is_experimental = self.is_experimental(model_name);
         ModelMetadata {
-            provider,
+            provider: provider.to_string(),
             series,
             model_type,
             variant,
@@ -242,25 +334,78 @@ impl ModelClassifier {
     }
 }
 
+/// A model name's version, as an ordered list of numeric components.
+///
+/// Parsing strips everything but digits, dots, and whitespace, splits on
+/// whitespace, then splits each resulting token on `.` and parses every
+/// piece as a `u32` — e.g. `"gemini-1.5-pro"` -> `[1, 5]`, `"gpt-4o"` -> `[4]`.
+/// `Ord` compares component-by-component, so a shorter-but-equal prefix
+/// sorts smaller (`1.5 < 1.5.1`), which is exactly what `Vec<u32>`'s
+/// derived lexicographic `Ord` already gives us.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ModelVersion(Vec<u32>);
+
+impl ModelVersion {
+    /// Parses a model name into its ordered numeric version components
+    pub fn parse(model_name: &str) -> Self {
+        let filtered: String = model_name
+            .chars()
+            .map(|c| if c.is_ascii_digit() || c == '.' { c } else { ' ' })
+            .collect();
+        let components = filtered
+            .split_whitespace()
+            .flat_map(|token| token.split('.'))
+            .filter_map(|part| part.parse::<u32>().ok())
+            .collect();
+        ModelVersion(components)
+    }
+
+    /// True if no numeric version components were found
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl std::fmt::Display for ModelVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let parts: Vec<String> = self.0.iter().map(|n| n.to_string()).collect();
+        write!(f, "{}", parts.join("."))
+    }
+}
+
 /// Extract version variant like "Series 4.5"
 fn extract_version_variant(model_name: &str, series: &str) -> String {
-    let nums = extract_version_numbers(model_name);
-    if nums.is_empty() { return String::new(); }
-    let vs: Vec<String> = nums.iter().map(|n| n.to_string()).collect();
-    format!("{} {}", series, vs.join("."))
+    let version = ModelVersion::parse(model_name);
+    if version.is_empty() { return String::new(); }
+    format!("{} {}", series, version)
 }
 
-/// Extract numeric version parts
-fn extract_version_numbers(s: &str) -> Vec<i32> {
-    let filtered: String = s.chars()
-        .map(|c| if c.is_digit(10) || c == '.' { c } else { ' ' })
-        .collect();
-    filtered.split_whitespace().filter_map(|part| part.parse::<i32>().ok()).collect()
+/// Compare version strings, newest first
+fn is_newer_version(a: &str, b: &str) -> bool {
+    ModelVersion::parse(a) > ModelVersion::parse(b)
 }
 
-/// Compare version strings
-fn is_newer_version(a: &str, b: &str) -> bool {
-    let na = extract_version_numbers(a);
-    let nb = extract_version_numbers(b);
-    na > nb
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_extracts_numeric_components_in_order() {
+        assert_eq!(ModelVersion::parse("gemini-1.5-pro"), ModelVersion(vec![1, 5]));
+        assert_eq!(ModelVersion::parse("gpt-4o"), ModelVersion(vec![4]));
+        assert_eq!(ModelVersion::parse("claude-3-opus"), ModelVersion(vec![3]));
+        assert!(ModelVersion::parse("no-digits-here").is_empty());
+    }
+
+    #[test]
+    fn shorter_equal_prefix_sorts_smaller() {
+        assert!(ModelVersion::parse("1.5") < ModelVersion::parse("1.5.1"));
+        assert!(ModelVersion::parse("gemini-1.5") < ModelVersion::parse("gemini-1.5.1"));
+    }
+
+    #[test]
+    fn is_newer_version_compares_numerically_not_lexically() {
+        assert!(is_newer_version("gpt-4.10", "gpt-4.9"));
+        assert!(!is_newer_version("gpt-3.5", "gpt-4"));
+    }
 } 
\ No newline at end of file
@@ -1,12 +1,14 @@
+use crate::config::{ConfigError, FamilyHeuristic, ModelConfig};
 use std::collections::HashMap;
 
 /// Handles determining the context window size for models
 pub struct ContextResolver {
     context_sizes: HashMap<String, i32>,
+    family_heuristics: Vec<FamilyHeuristic>,
 }
 
 impl ContextResolver {
-    /// Creates a new context window size resolver
+    /// Creates a new context window size resolver using the built-in tables
     pub fn new() -> Self {
         let mut context_sizes = HashMap::new();
         // OpenAI
@@ -37,7 +39,33 @@ impl ContextResolver {
         context_sizes.insert("gemini-2.0-flash".into(), 1_000_000);
         context_sizes.insert("gemini-2.0-flash-lite".into(), 1_000_000);
 
-        ContextResolver { context_sizes }
+        ContextResolver { context_sizes, family_heuristics: Vec::new() }
+    }
+
+    /// Builds a context window size resolver from an externally loaded config,
+    /// replacing the built-in tables with the config's when they're non-empty
+    pub fn from_config(cfg: &ModelConfig) -> Self {
+        let context_sizes = if cfg.context_sizes.is_empty() {
+            Self::new().context_sizes
+        } else {
+            cfg.context_sizes
+                .iter()
+                .map(|(k, v)| (k.to_lowercase(), *v))
+                .collect()
+        };
+        ContextResolver {
+            context_sizes,
+            family_heuristics: cfg.family_heuristics.clone(),
+        }
+    }
+
+    /// Loads the `MODEL_CONFIG_FILE` config if set and builds from it,
+    /// otherwise falls back to the built-in tables
+    pub fn load() -> Result<Self, ConfigError> {
+        Ok(match crate::config::load_from_env()? {
+            Some(cfg) => Self::from_config(&cfg),
+            None => Self::new(),
+        })
     }
 
     /// Determine a model's context window based on its ID
@@ -55,6 +83,12 @@ impl ContextResolver {
 
     /// Heuristics for common model families
     fn get_context_size_by_family(&self, lower: &str) -> i32 {
+        // Config-supplied heuristics take priority over the built-in ones
+        for heuristic in &self.family_heuristics {
+            if lower.contains(&heuristic.pattern.to_lowercase()) {
+                return heuristic.context_size;
+            }
+        }
         // GPT family
         if lower.contains("gpt-4.5") {
             return 128000;
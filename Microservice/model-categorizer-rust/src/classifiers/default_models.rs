@@ -1,3 +1,4 @@
+use crate::config::{ConfigError, ModelConfig};
 use std::collections::HashSet;
 
 /// Handles detection of default model configurations
@@ -6,7 +7,7 @@ pub struct DefaultModels {
 }
 
 impl DefaultModels {
-    /// Creates a new default model detector
+    /// Creates a new default model detector using the built-in list
     pub fn new() -> Self {
         let models = [
             "gpt-3.5-turbo",
@@ -22,6 +23,26 @@ impl DefaultModels {
         DefaultModels { default_models }
     }
 
+    /// Builds a default model detector from an externally loaded config,
+    /// replacing the built-in list when the config's is non-empty
+    pub fn from_config(cfg: &ModelConfig) -> Self {
+        if cfg.default_models.is_empty() {
+            return Self::new();
+        }
+        DefaultModels {
+            default_models: cfg.default_models.iter().cloned().collect(),
+        }
+    }
+
+    /// Loads the `MODEL_CONFIG_FILE` config if set and builds from it,
+    /// otherwise falls back to the built-in list
+    pub fn load() -> Result<Self, ConfigError> {
+        Ok(match crate::config::load_from_env()? {
+            Some(cfg) => Self::from_config(&cfg),
+            None => Self::new(),
+        })
+    }
+
     /// Checks if a model ID corresponds to a default model
     pub fn is_default_model(&self, model_id: &str) -> bool {
         if self.default_models.contains(model_id) {
@@ -57,7 +57,7 @@ pub mod default_models;
 pub mod classifier;
 
 // Exports
-pub use pattern_matcher::PatternMatcher;
+pub use pattern_matcher::{PatternMatcher, PatternRegistryConfig};
 pub use context_resolver::ContextResolver;
 pub use default_models::DefaultModels;
-pub use classifier::ModelClassifier; 
\ No newline at end of file
+pub use classifier::{ModelClassifier, ModelVersion}; 
\ No newline at end of file
@@ -1,3 +1,5 @@
+use crate::config::ConfigError;
+use serde::Deserialize;
 use std::collections::HashMap;
 use super::{
     PROVIDER_OPENAI,
@@ -40,12 +42,32 @@ use super::{
     VERSION_45,
 };
 
+/// Serde schema for an externally loaded pattern registry, overlaid onto
+/// [`PatternMatcher`]'s built-in tables by [`PatternMatcher::from_config`]
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PatternRegistryConfig {
+    #[serde(default)]
+    pub provider_patterns: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub series_patterns: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub type_patterns: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub capability_patterns: HashMap<String, Vec<String>>,
+}
+
 /// PatternMatcher handles all pattern-based identification for models
 pub struct PatternMatcher {
     pub provider_patterns: HashMap<String, Vec<String>>,
     pub series_patterns: HashMap<String, Vec<String>>,
     pub type_patterns: HashMap<String, Vec<String>>,
     pub capability_patterns: HashMap<String, Vec<String>>,
+    // Declared tie-break order for the `match_*_by_pattern` entry points:
+    // when two candidate patterns tie on specificity (matched substring
+    // length), the one appearing earlier here wins.
+    provider_priority: Vec<String>,
+    series_priority: Vec<String>,
+    type_priority: Vec<String>,
 }
 
 impl PatternMatcher {
@@ -94,7 +116,140 @@ impl PatternMatcher {
         capability_patterns.insert("audio".into(), vec!["whisper".into(), "tts".into(), "speech".into(), "audio".into()]);
         capability_patterns.insert(CAP_CHAT.to_string(), vec!["chat".into(), "conversation".into(), "completion".into()]);
 
-        PatternMatcher { provider_patterns, series_patterns, type_patterns, capability_patterns }
+        let provider_priority = vec![
+            PROVIDER_OPENAI.to_string(),
+            PROVIDER_ANTHROPIC.to_string(),
+            PROVIDER_GEMINI.to_string(),
+            PROVIDER_META.to_string(),
+            PROVIDER_MISTRAL.to_string(),
+            PROVIDER_OPENROUTER.to_string(),
+        ];
+        let series_priority = vec![
+            SERIES_CLAUDE3.to_string(),
+            SERIES_CLAUDE2.to_string(),
+            SERIES_CLAUDE1.to_string(),
+            format!("{} {}", "Gemini", VERSION_25),
+            format!("{} {}", "Gemini", VERSION_20),
+            format!("{} {}", "Gemini", VERSION_15),
+            format!("{} {}", "Gemini", VERSION_10),
+            "Gemma 2".to_string(),
+            TYPE_IMAGE.to_string(),
+            TYPE_EMBEDDING.to_string(),
+        ];
+        let type_priority = vec![
+            TYPE_FLASH_LITE.to_string(),
+            TYPE_45.to_string(),
+            TYPE_4.to_string(),
+            TYPE_35.to_string(),
+            TYPE_O.to_string(),
+            TYPE_MINI.to_string(),
+            TYPE_OPUS.to_string(),
+            TYPE_SONNET.to_string(),
+            TYPE_HAIKU.to_string(),
+            TYPE_THINKING.to_string(),
+            TYPE_FLASH.to_string(),
+            TYPE_PRO.to_string(),
+            TYPE_VISION.to_string(),
+            TYPE_EMBEDDING.to_string(),
+        ];
+
+        PatternMatcher {
+            provider_patterns,
+            series_patterns,
+            type_patterns,
+            capability_patterns,
+            provider_priority,
+            series_priority,
+            type_priority,
+        }
+    }
+
+    /// Loads a pattern registry from `path_or_str` — a path to a TOML/JSON
+    /// file if one exists at that location, otherwise the TOML/JSON text
+    /// itself — and overlays it on top of the built-in defaults, so a
+    /// newly released vendor or model family can be registered at startup
+    /// without a recompile.
+    pub fn from_config(path_or_str: &str) -> Result<Self, ConfigError> {
+        let contents = if std::path::Path::new(path_or_str).is_file() {
+            std::fs::read_to_string(path_or_str).map_err(|source| ConfigError::Io {
+                path: path_or_str.to_string(),
+                source,
+            })?
+        } else {
+            path_or_str.to_string()
+        };
+        let cfg: PatternRegistryConfig = if contents.trim_start().starts_with('{') {
+            serde_json::from_str(&contents).map_err(|e| ConfigError::Parse {
+                path: path_or_str.to_string(),
+                message: e.to_string(),
+            })?
+        } else {
+            toml::from_str(&contents).map_err(|e| ConfigError::Parse {
+                path: path_or_str.to_string(),
+                message: e.to_string(),
+            })?
+        };
+        Ok(Self::new().merge(cfg))
+    }
+
+    /// Overlays a parsed pattern registry on top of `self`: patterns for a
+    /// key that already exists are appended to it, and a brand-new key is
+    /// inserted with its priority placed ahead of the built-ins so it wins
+    /// any specificity ties against them.
+    fn merge(mut self, cfg: PatternRegistryConfig) -> Self {
+        Self::merge_table(&mut self.provider_patterns, &mut self.provider_priority, cfg.provider_patterns);
+        Self::merge_table(&mut self.series_patterns, &mut self.series_priority, cfg.series_patterns);
+        Self::merge_table(&mut self.type_patterns, &mut self.type_priority, cfg.type_patterns);
+        for (key, patterns) in cfg.capability_patterns {
+            self.capability_patterns.entry(key).or_default().extend(patterns);
+        }
+        self
+    }
+
+    fn merge_table(
+        table: &mut HashMap<String, Vec<String>>,
+        priority: &mut Vec<String>,
+        overlay: HashMap<String, Vec<String>>,
+    ) {
+        for (key, mut patterns) in overlay {
+            match table.get_mut(&key) {
+                Some(existing) => existing.append(&mut patterns),
+                None => {
+                    table.insert(key.clone(), patterns);
+                    priority.insert(0, key);
+                }
+            }
+        }
+    }
+
+    /// Ranks every (key, pattern) pair in `table` whose pattern is a
+    /// substring of `lower`, and returns the key of the best match: longest
+    /// matched pattern wins, ties broken by position in `priority` (earlier
+    /// wins), ties on that broken by key name for full determinism.
+    fn best_pattern_match(lower: &str, table: &HashMap<String, Vec<String>>, priority: &[String]) -> String {
+        let mut best: Option<(usize, usize, &str)> = None;
+        for (key, patterns) in table {
+            for pat in patterns {
+                if lower.contains(pat.as_str()) {
+                    let rank = priority.iter().position(|p| p == key).unwrap_or(priority.len());
+                    let candidate = (pat.len(), rank, key.as_str());
+                    best = Some(match best {
+                        None => candidate,
+                        Some(current) => {
+                            if candidate.0 > current.0
+                                || (candidate.0 == current.0 && candidate.1 < current.1)
+                                || (candidate.0 == current.0 && candidate.1 == current.1 && candidate.2 < current.2)
+                            {
+                                candidate
+                            } else {
+                                current
+                            }
+                        }
+                    });
+                }
+            }
+        }
+        best.map(|(_, _, k)| k.to_string()).unwrap_or_default()
     }
 
     /// Match a provider by exact name
@@ -107,17 +262,11 @@ impl PatternMatcher {
         "".into()
     }
 
-    /// Match a provider based on patterns in the model name
+    /// Match a provider based on patterns in the model name, preferring the
+    /// longest matched pattern (see [`Self::best_pattern_match`])
     pub fn match_provider_by_pattern(&self, model_name: &str) -> String {
         let lower = model_name.to_lowercase();
-        for (provider, patterns) in &self.provider_patterns {
-            for pat in patterns {
-                if lower.contains(pat) {
-                    return provider.clone();
-                }
-            }
-        }
-        "".into()
+        Self::best_pattern_match(&lower, &self.provider_patterns, &self.provider_priority)
     }
 
     /// Match Claude series versions
@@ -138,15 +287,11 @@ impl PatternMatcher {
         format!("{} {}", "Gemini", VERSION_10)
     }
 
-    /// Match series by generic patterns
+    /// Match series by generic patterns, preferring the longest matched
+    /// pattern (see [`Self::best_pattern_match`])
     pub fn match_series_by_pattern(&self, model_name: &str) -> String {
         let lower = model_name.to_lowercase();
-        for (series, patterns) in &self.series_patterns {
-            for pat in patterns {
-                if lower.contains(pat) { return series.clone() }
-            }
-        }
-        "".into()
+        Self::best_pattern_match(&lower, &self.series_patterns, &self.series_priority)
     }
 
     /// Match OpenAI-specific type
@@ -180,15 +325,12 @@ impl PatternMatcher {
         TYPE_STANDARD.into()
     }
 
-    /// Generic type matching by patterns
+    /// Generic type matching by patterns, preferring the longest matched
+    /// pattern (see [`Self::best_pattern_match`]) — this is what makes
+    /// `flash-lite` win over `flash` regardless of HashMap iteration order
     pub fn match_type_by_pattern(&self, model_name: &str) -> String {
         let lower = model_name.to_lowercase();
-        for (typ, patterns) in &self.type_patterns {
-            for pat in patterns {
-                if lower.contains(pat) { return typ.clone() }
-            }
-        }
-        "".into()
+        Self::best_pattern_match(&lower, &self.type_patterns, &self.type_priority)
     }
 
     /// Match OpenAI variant
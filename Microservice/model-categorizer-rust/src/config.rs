@@ -0,0 +1,82 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A family-level fallback rule: if `pattern` is a substring of the
+/// lowercased model id, `context_size` is used when no exact entry in
+/// `context_sizes` matched.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FamilyHeuristic {
+    pub pattern: String,
+    pub context_size: i32,
+}
+
+/// External, hot-editable source for data that would otherwise be baked
+/// into `ContextResolver` and `DefaultModels` at compile time.
+///
+/// Loaded from the TOML/YAML file referenced by `MODEL_CONFIG_FILE`; any
+/// section left empty falls back to the crate's built-in tables.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ModelConfig {
+    #[serde(default)]
+    pub context_sizes: HashMap<String, i32>,
+    #[serde(default)]
+    pub family_heuristics: Vec<FamilyHeuristic>,
+    #[serde(default)]
+    pub default_models: Vec<String>,
+}
+
+/// Errors that can occur while locating, reading, or parsing a model config file
+#[derive(Debug)]
+pub enum ConfigError {
+    Io { path: String, source: std::io::Error },
+    Parse { path: String, message: String },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, source } => {
+                write!(f, "failed to read model config file '{}': {}", path, source)
+            }
+            ConfigError::Parse { path, message } => {
+                write!(f, "failed to parse model config file '{}': {}", path, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Loads the `ModelConfig` referenced by `MODEL_CONFIG_FILE`, if set.
+///
+/// Returns `Ok(None)` when the env var is unset so callers can fall back to
+/// the built-in tables; returns `Err` with a descriptive message when the
+/// file is set but missing or malformed, so startup fails loudly instead of
+/// silently running with a half-loaded config.
+pub fn load_from_env() -> Result<Option<ModelConfig>, ConfigError> {
+    match std::env::var("MODEL_CONFIG_FILE") {
+        Ok(path) => load_from_path(&path).map(Some),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Parses a `ModelConfig` from a file path, dispatching on extension
+/// (`.yaml`/`.yml` for YAML, anything else as TOML).
+pub fn load_from_path(path: &str) -> Result<ModelConfig, ConfigError> {
+    let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+        path: path.to_string(),
+        source,
+    })?;
+    if path.ends_with(".yaml") || path.ends_with(".yml") {
+        serde_yaml::from_str(&contents).map_err(|e| ConfigError::Parse {
+            path: path.to_string(),
+            message: e.to_string(),
+        })
+    } else {
+        toml::from_str(&contents).map_err(|e| ConfigError::Parse {
+            path: path.to_string(),
+            message: e.to_string(),
+        })
+    }
+}
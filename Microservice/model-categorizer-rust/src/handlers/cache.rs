@@ -0,0 +1,244 @@
+use crate::models::HierarchicalModelGroup;
+use crate::proto::modelservice::Model as ProtoModel;
+use lru::LruCache;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Hashes the incoming models' full request-supplied state (not just id and
+/// provider) plus `config_salt`, so the key changes whenever anything that
+/// feeds into the cached response changes: any response-affecting field on
+/// any model, or the active `MODEL_CONFIG_FILE` content. Independent of the
+/// order models arrived in.
+pub fn cache_key(models: &[ProtoModel], config_salt: u64) -> u64 {
+    let mut rows: Vec<String> = models
+        .iter()
+        .map(|m| {
+            // `capabilities` is deliberately excluded: `enrich_models` always
+            // overwrites it with classifier-derived values, so the
+            // caller-supplied input never reaches the response and including
+            // it would only cause needless cache misses.
+            let mut metadata: Vec<(String, String)> = m.metadata.clone().into_iter().collect();
+            metadata.sort();
+            format!(
+                "{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{}|{:?}",
+                m.provider,
+                m.id,
+                m.name,
+                m.display_name,
+                m.context_size,
+                m.max_tokens,
+                m.cost_per_token,
+                m.description,
+                m.family,
+                m.version,
+                m.is_default,
+                metadata,
+            )
+        })
+        .collect();
+    rows.sort();
+    let mut hasher = DefaultHasher::new();
+    rows.hash(&mut hasher);
+    config_salt.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Hashes the contents of `MODEL_CONFIG_FILE` (empty if unset or unreadable)
+/// so a cache built before an edit to that file doesn't serve stale entries
+/// after a restart picks up the new content
+pub fn config_salt() -> u64 {
+    let mut hasher = DefaultHasher::new();
+    if let Ok(path) = std::env::var("MODEL_CONFIG_FILE") {
+        if let Ok(bytes) = std::fs::read(path) {
+            bytes.hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+/// Cache of fully built hierarchical classification results, keyed by a hash
+/// of the incoming models' full state (see [`cache_key`]).
+///
+/// Entries are archived with `rkyv` and written to disk so the service can
+/// warm-start across restarts. A cached result is read back by validating
+/// the archive with `rkyv`'s `validation` feature and then deserializing it
+/// into owned groups; hits are not served directly from the archived bytes.
+/// An in-memory LRU sits in front so repeat hits never touch disk.
+pub struct ClassificationCache {
+    memory: Mutex<LruCache<u64, Vec<u8>>>,
+    dir: PathBuf,
+}
+
+impl ClassificationCache {
+    /// Creates a cache with the given in-memory capacity, persisting entries
+    /// under `dir` (created if missing)
+    pub fn new(capacity: usize, dir: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::new(1).unwrap());
+        Ok(Self {
+            memory: Mutex::new(LruCache::new(capacity)),
+            dir,
+        })
+    }
+
+    fn path_for(&self, key: u64) -> PathBuf {
+        self.dir.join(format!("{:016x}.rkyv", key))
+    }
+
+    /// Looks up a cached result, checking the in-memory LRU first and
+    /// falling back to the on-disk archive
+    pub fn get(&self, key: u64) -> Option<Vec<HierarchicalModelGroup>> {
+        if let Some(bytes) = self.memory.lock().unwrap().get(&key) {
+            return Self::decode(bytes);
+        }
+        let bytes = std::fs::read(self.path_for(key)).ok()?;
+        let groups = Self::decode(&bytes)?;
+        self.memory.lock().unwrap().put(key, bytes);
+        Some(groups)
+    }
+
+    /// Stores a result in both the in-memory LRU and on disk as an
+    /// rkyv-archived blob
+    pub fn put(&self, key: u64, groups: &[HierarchicalModelGroup]) {
+        let owned = groups.to_vec();
+        let bytes = match rkyv::to_bytes::<_, 4096>(&owned) {
+            Ok(b) => b.into_vec(),
+            Err(_) => return,
+        };
+        if std::fs::write(self.path_for(key), &bytes).is_err() {
+            return;
+        }
+        self.memory.lock().unwrap().put(key, bytes);
+    }
+
+    /// Validates and deserializes an archived blob back into owned groups
+    fn decode(bytes: &[u8]) -> Option<Vec<HierarchicalModelGroup>> {
+        let archived = rkyv::check_archived_root::<Vec<HierarchicalModelGroup>>(bytes).ok()?;
+        archived.deserialize(&mut rkyv::Infallible).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Model;
+    use std::collections::HashMap;
+
+    fn sample_proto_model(id: &str, provider: &str) -> ProtoModel {
+        ProtoModel {
+            id: id.to_string(),
+            name: String::new(),
+            context_size: 128_000,
+            max_tokens: 4096,
+            provider: provider.to_string(),
+            display_name: String::new(),
+            description: String::new(),
+            cost_per_token: 0.00001,
+            capabilities: vec!["vision".to_string(), "chat".to_string()],
+            family: String::new(),
+            r#type: String::new(),
+            series: String::new(),
+            variant: String::new(),
+            is_default: false,
+            is_multimodal: true,
+            is_experimental: false,
+            version: String::new(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn sample_group(id: &str) -> HierarchicalModelGroup {
+        HierarchicalModelGroup {
+            group_name: "provider".to_string(),
+            group_value: "openai".to_string(),
+            models: vec![Model {
+                id: id.to_string(),
+                name: None,
+                context_size: 128_000,
+                max_tokens: 4096,
+                provider: "openai".to_string(),
+                original_provider: None,
+                display_name: None,
+                description: None,
+                cost_per_token: 0.00001,
+                capabilities: vec!["chat".to_string()],
+                family: None,
+                model_type: None,
+                series: None,
+                variant: None,
+                is_default: false,
+                is_multimodal: false,
+                is_experimental: false,
+                version: None,
+                metadata: HashMap::new(),
+            }],
+            children: Vec::new(),
+        }
+    }
+
+    fn temp_cache_dir(label: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("classification_cache_test_{}_{}", label, std::process::id()))
+    }
+
+    #[test]
+    fn cache_key_is_order_independent_and_sensitive_to_model_state() {
+        let a = sample_proto_model("gpt-4o", "openai");
+        let b = sample_proto_model("gpt-4o-mini", "openai");
+
+        assert_eq!(cache_key(&[a.clone(), b.clone()], 0), cache_key(&[b.clone(), a.clone()], 0));
+
+        let mut changed = b.clone();
+        changed.context_size = 8_000;
+        assert_ne!(cache_key(&[a.clone(), b.clone()], 0), cache_key(&[a.clone(), changed.clone()], 0));
+
+        let mut renamed = b.clone();
+        renamed.name = "GPT-4o mini".to_string();
+        assert_ne!(cache_key(&[a.clone(), b.clone()], 0), cache_key(&[a.clone(), renamed.clone()], 0));
+
+        let mut redisplayed = b.clone();
+        redisplayed.display_name = "Mini".to_string();
+        assert_ne!(cache_key(&[a.clone(), b.clone()], 0), cache_key(&[a.clone(), redisplayed], 0));
+
+        // Input capabilities are always overwritten by enrichment before they
+        // reach the response, so they must not affect the key
+        let mut recapped = b.clone();
+        recapped.capabilities = vec!["embedding".to_string()];
+        assert_eq!(cache_key(&[a.clone(), b.clone()], 0), cache_key(&[a.clone(), recapped], 0));
+
+        assert_ne!(cache_key(&[a.clone(), b.clone()], 0), cache_key(&[a, b], 1));
+    }
+
+    #[test]
+    fn put_then_get_round_trips_through_rkyv() {
+        let dir = temp_cache_dir("roundtrip");
+        let cache = ClassificationCache::new(4, &dir).expect("cache dir should be creatable");
+        let groups = vec![sample_group("gpt-4o")];
+
+        cache.put(42, &groups);
+        let fetched = cache.get(42).expect("entry should be present after put");
+
+        assert_eq!(fetched.len(), groups.len());
+        assert_eq!(fetched[0].group_value, groups[0].group_value);
+        assert_eq!(fetched[0].models[0].id, groups[0].models[0].id);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn get_reads_back_through_disk_when_evicted_from_memory() {
+        let dir = temp_cache_dir("disk_fallback");
+        let cache = ClassificationCache::new(1, &dir).expect("cache dir should be creatable");
+        cache.put(1, &[sample_group("a")]);
+        // Capacity 1 evicts key 1 from the in-memory LRU, leaving only the disk copy
+        cache.put(2, &[sample_group("b")]);
+
+        let fetched = cache.get(1).expect("entry should still be readable from disk");
+        assert_eq!(fetched[0].models[0].id, "a");
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
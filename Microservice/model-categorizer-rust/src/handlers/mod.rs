@@ -1,4 +1,5 @@
 // Declare utilities sub-module
+mod cache;
 mod utils;
 
 // External imports
@@ -12,6 +13,7 @@ use crate::proto::modelservice::model_classification_service_server::ModelClassi
 // Internal imports
 use crate::classifiers::ModelClassifier;
 use crate::classifiers::TYPE_STANDARD;
+use crate::metrics::{self, RpcTimer};
 use crate::handlers::utils::{
     convert_proto_models_to_internal,
     convert_internal_hierarchical_group_to_proto,
@@ -20,35 +22,60 @@ use crate::handlers::utils::{
     bool_to_yes_no,
     filter_models_by_criteria,
     sort_models,
+    SortConfig,
     classify_models_by_property,
+    build_hierarchical_groups,
 };
 use crate::models::{available_classification_properties, Model, HierarchicalModelGroup};
+use cache::ClassificationCache;
+
+/// Default in-memory capacity for the classification cache, overridable via
+/// `CLASSIFICATION_CACHE_CAPACITY`
+const DEFAULT_CACHE_CAPACITY: usize = 256;
+/// Default on-disk location for archived classification cache entries,
+/// overridable via `CLASSIFICATION_CACHE_DIR`
+const DEFAULT_CACHE_DIR: &str = "data/classification_cache";
 
 /// gRPC handler for model classification
 pub struct ModelClassificationHandler {
     classifier: ModelClassifier,
+    cache: ClassificationCache,
+    // Hash of `MODEL_CONFIG_FILE`'s content at startup, folded into every
+    // cache key so a restart after editing that file invalidates old entries
+    // instead of serving stale classifications indefinitely
+    cache_config_salt: u64,
 }
 
 impl Default for ModelClassificationHandler {
     fn default() -> Self {
-        Self { classifier: ModelClassifier::new() }
+        Self {
+            classifier: ModelClassifier::new(),
+            cache: new_cache_from_env(),
+            cache_config_salt: cache::config_salt(),
+        }
     }
 }
 
-#[async_trait]
-impl ModelClassificationService for ModelClassificationHandler {
-    /// Hierarchical classification endpoint
-    async fn classify_models(
-        &self,
-        request: Request<LoadedModelList>,
-    ) -> Result<Response<ClassifiedModelResponse>, Status> {
-        let req = request.into_inner();
-        // Convert to internal models
-        let mut internal = convert_proto_models_to_internal(&req.models);
-        // Enhance models with classifier metadata
-        for m in &mut internal {
+impl ModelClassificationHandler {
+    /// Builds a handler whose classifier loads context windows and default
+    /// models from `MODEL_CONFIG_FILE` when set, so startup fails loudly on
+    /// a malformed config instead of silently falling back
+    pub fn from_env() -> Result<Self, crate::config::ConfigError> {
+        Ok(Self {
+            classifier: ModelClassifier::from_env()?,
+            cache: new_cache_from_env(),
+            cache_config_salt: cache::config_salt(),
+        })
+    }
+
+    /// Populates classifier-derived fields on each model in place, recording
+    /// a per-provider classification count and the number of models received
+    /// for `rpc`. Shared by both RPC endpoints so
+    /// `classify_models_with_criteria` enriches models the same way
+    /// `classify_models` does before filtering/grouping them.
+    fn enrich_models(&self, models: &mut [Model], rpc: &'static str) {
+        for m in models.iter_mut() {
             let meta = self.classifier.classify_model(&m.id, &m.provider);
-            // apply classification metadata fields onto m
             m.provider = meta.provider;
             m.series = Some(meta.series.clone());
             m.model_type = Some(meta.model_type.clone());
@@ -64,9 +91,61 @@ impl ModelClassificationService for ModelClassificationHandler {
             if m.display_name.is_none() {
                 m.display_name = Some(meta.display_name.clone());
             }
+            metrics::MODELS_CLASSIFIED_BY_PROVIDER
+                .with_label_values(&[m.provider.as_str()])
+                .inc();
         }
-        // Build hierarchical groups
-        let root_groups = build_model_hierarchy(&internal);
+        metrics::MODELS_RECEIVED_TOTAL
+            .with_label_values(&[rpc])
+            .inc_by(models.len() as u64);
+    }
+}
+
+/// Builds the classification cache from `CLASSIFICATION_CACHE_DIR` /
+/// `CLASSIFICATION_CACHE_CAPACITY`, falling back to their defaults
+fn new_cache_from_env() -> ClassificationCache {
+    let dir = std::env::var("CLASSIFICATION_CACHE_DIR").unwrap_or_else(|_| DEFAULT_CACHE_DIR.to_string());
+    let capacity = std::env::var("CLASSIFICATION_CACHE_CAPACITY")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_CACHE_CAPACITY);
+    ClassificationCache::new(capacity, dir).expect("failed to initialize classification cache directory")
+}
+
+#[async_trait]
+impl ModelClassificationService for ModelClassificationHandler {
+    /// Hierarchical classification endpoint
+    async fn classify_models(
+        &self,
+        request: Request<LoadedModelList>,
+    ) -> Result<Response<ClassifiedModelResponse>, Status> {
+        let timer = RpcTimer::start(metrics::RPC_CLASSIFY_MODELS);
+        let req = request.into_inner();
+        // A repeated identical model set hashes to the same key regardless of
+        // ordering, so cache hits skip both classifier enrichment and hierarchy
+        // build. The key covers every response-affecting field plus the active
+        // config salt, so a differing request or a changed `MODEL_CONFIG_FILE`
+        // can't be served another request's stale cached result.
+        let key = cache::cache_key(&req.models, self.cache_config_salt);
+        let root_groups = if let Some(cached) = self.cache.get(key) {
+            // enrich_models is skipped on a hit, so count the received models here
+            metrics::MODELS_RECEIVED_TOTAL
+                .with_label_values(&[metrics::RPC_CLASSIFY_MODELS])
+                .inc_by(req.models.len() as u64);
+            cached
+        } else {
+            // Convert to internal models
+            let mut internal = convert_proto_models_to_internal(&req.models);
+            // Enhance models with classifier metadata
+            self.enrich_models(&mut internal, metrics::RPC_CLASSIFY_MODELS);
+            // Build hierarchical groups
+            let groups = build_model_hierarchy(&internal);
+            self.cache.put(key, &groups);
+            groups
+        };
+        metrics::GROUPS_PRODUCED_TOTAL
+            .with_label_values(&[metrics::RPC_CLASSIFY_MODELS])
+            .inc_by(root_groups.len() as u64);
         // Convert to proto
         let proto_groups: Vec<_> = root_groups.iter()
             .map(convert_internal_hierarchical_group_to_proto)
@@ -81,17 +160,77 @@ impl ModelClassificationService for ModelClassificationHandler {
             error_message: String::new(),
             hierarchical_groups: proto_groups,
         };
+        timer.success();
         Ok(Response::new(response))
     }
 
-    /// Classification with filtering criteria
+    /// Classification with filtering criteria, returning both the flat
+    /// `classified_groups` keyed on the requested property and, when
+    /// `hierarchical` is set on the criteria, the nested `hierarchical_groups`
     async fn classify_models_with_criteria(
         &self,
         request: Request<ClassificationCriteria>,
     ) -> Result<Response<ClassifiedModelResponse>, Status> {
-        let _req = request.into_inner();
-        // TODO: implement filtering and both flat/hierarchical classification as in Go version
-        Err(Status::unimplemented("ClassifyModelsWithCriteria is not yet implemented"))
+        let timer = RpcTimer::start(metrics::RPC_CLASSIFY_MODELS_WITH_CRITERIA);
+        let req = request.into_inner();
+        let props = available_classification_properties();
+        let available = convert_to_proto_properties(&props);
+
+        // Convert and enrich, same as classify_models
+        let mut internal = convert_proto_models_to_internal(&req.models);
+        self.enrich_models(&mut internal, metrics::RPC_CLASSIFY_MODELS_WITH_CRITERIA);
+
+        // Apply filtering criteria (min context size, experimental/deprecated
+        // flags, required/any capability sets)
+        let filtered = filter_models_by_criteria(&internal, &req);
+
+        // Every requested property feeds either the flat classification or,
+        // in hierarchical mode, a level of the nested tree, so reject the
+        // whole request up front if any of them is unknown instead of
+        // letting `build_hierarchical_groups` silently drop every model at
+        // a bad level further down.
+        if let Some(unknown) = req.properties.iter().find(|p| !props.iter().any(|avail| &avail.name == *p)) {
+            // The client asked for a property we don't recognize: this is a
+            // rejected request, not a successful one, so it must count
+            // against the error rate rather than inflate the success rate
+            timer.error();
+            return Ok(Response::new(ClassifiedModelResponse {
+                classified_groups: Vec::new(),
+                available_properties: available,
+                error_message: format!("unknown classification property '{}'", unknown),
+                hierarchical_groups: Vec::new(),
+            }));
+        }
+
+        // Classify by the first requested property, defaulting to "provider"
+        let property = req.properties.first().cloned().unwrap_or_else(|| "provider".to_string());
+        let classified_groups = classify_models_by_property(&filtered, &property);
+
+        // Only build the nested tree when the caller asked for it. When the
+        // request names properties to group by, nest on exactly those in
+        // order; otherwise fall back to the built-in provider/type/version tree.
+        let hierarchical_groups = if req.hierarchical {
+            let tree = if req.properties.is_empty() {
+                build_model_hierarchy(&filtered)
+            } else {
+                build_hierarchical_groups(&filtered, &req.properties)
+            };
+            tree.iter().map(convert_internal_hierarchical_group_to_proto).collect()
+        } else {
+            Vec::new()
+        };
+        metrics::GROUPS_PRODUCED_TOTAL
+            .with_label_values(&[metrics::RPC_CLASSIFY_MODELS_WITH_CRITERIA])
+            .inc_by(hierarchical_groups.len() as u64);
+
+        let response = ClassifiedModelResponse {
+            classified_groups,
+            available_properties: available,
+            error_message: String::new(),
+            hierarchical_groups,
+        };
+        timer.success();
+        Ok(Response::new(response))
     }
 }
 
@@ -101,7 +240,7 @@ fn build_model_hierarchy(
 ) -> Vec<crate::models::HierarchicalModelGroup> {
     // Clone and sort models
     let mut sorted = models.to_vec();
-    sort_models(&mut sorted);
+    sort_models(&mut sorted, &SortConfig::default());
     let mut root_groups: Vec<crate::models::HierarchicalModelGroup> = Vec::new();
     let mut provider_idx: Option<usize> = None;
     let mut type_idx: Option<usize> = None;
@@ -1,3 +1,4 @@
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::cmp::Ordering;
 use crate::models::{Model as InternalModel, HierarchicalModelGroup as InternalHierarchicalModelGroup};
@@ -117,6 +118,17 @@ pub fn categorize_context_window(size: i32) -> String {
     }
 }
 
+/// Categorize cost per token into a display tier
+pub fn categorize_cost_per_token(cost: f64) -> String {
+    match cost {
+        c if c <= 0.0 => "Free".to_string(),
+        c if c < 0.000001 => "Low (< $1/M)".to_string(),
+        c if c < 0.00001 => "Medium ($1-10/M)".to_string(),
+        c if c < 0.0001 => "High ($10-100/M)".to_string(),
+        _ => "Premium (> $100/M)".to_string(),
+    }
+}
+
 /// Convert boolean to Yes/No
 pub fn bool_to_yes_no(value: bool) -> String {
     if value { "Yes".to_string() } else { "No".to_string() }
@@ -141,52 +153,139 @@ pub fn filter_models_by_criteria(
                 }
             }
         }
+        if !criteria.required_capabilities.is_empty()
+            && !criteria.required_capabilities.iter().all(|cap| model.capabilities.contains(cap))
+        {
+            return false;
+        }
+        if !criteria.any_capabilities.is_empty()
+            && !contains_any(&model.capabilities, &criteria.any_capabilities)
+        {
+            return false;
+        }
         true
     }).collect()
 }
 
-/// Sort models by provider, type (with special rules), version, then name
-pub fn sort_models(models_list: &mut [InternalModel]) {
-    // Provider priority map
-    // Order: gemini < openai < openrouter < anthropic/claude < others
-    let provider_priority: HashMap<&str, i32> = [
-        ("gemini", 0),
-        ("openai", 1),
-        ("openrouter", 2),
-        ("anthropic", 3),
-        ("claude", 3),
-    ].iter().cloned().collect();
-
-    // Type priority maps for each provider
-    let gemini_type_priority: HashMap<&str, i32> = [
-        (TYPE_FLASH_LITE, 0),
-        (TYPE_FLASH, 1),
-        (TYPE_PRO, 2),
-        (TYPE_THINKING, 3),
-        (TYPE_GEMMA, 4),
-        (TYPE_STANDARD, 5),
-    ].iter().cloned().collect();
-    let openai_type_priority: HashMap<&str, i32> = [
-        (TYPE_MINI, 0),
-        (TYPE_O, 1),
-        (TYPE_45, 2),
-        (TYPE_4, 3),
-        (TYPE_35, 4),
-        ("other", 5),
-    ].iter().cloned().collect();
-    let claude_type_priority: HashMap<&str, i32> = [
-        (TYPE_SONNET, 0),
-        (TYPE_OPUS, 1),
-        (TYPE_HAIKU, 2),
-        ("other", 3),
-    ].iter().cloned().collect();
+/// Tie-break rule applied once provider, type, and version all compare
+/// equal. Only one rule exists today; the enum exists so a config source
+/// can select among future rules without changing `sort_models`'s signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TieBreakRule {
+    NameAscending,
+}
 
+impl Default for TieBreakRule {
+    fn default() -> Self {
+        TieBreakRule::NameAscending
+    }
+}
+
+/// Data-driven model sort ordering: provider priority, a per-provider type
+/// priority map, version-ordering direction, and the final tie-break rule.
+///
+/// `SortConfig::default()` reproduces the crate's built-in ordering; deploy
+/// a config source (TOML/JSON via `serde`) to add providers/types or
+/// reorder existing ones without a recompile. A provider missing from
+/// `provider_priority` ranks 100. A type missing from its provider's map
+/// falls back to that provider's `"other"` entry, and to 100 if there isn't
+/// one or the provider has no type map.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SortConfig {
+    pub provider_priority: HashMap<String, i32>,
+    pub type_priority: HashMap<String, HashMap<String, i32>>,
+    pub version_descending: bool,
+    pub tie_break: TieBreakRule,
+}
+
+impl Default for SortConfig {
+    fn default() -> Self {
+        // Order: gemini < openai < openrouter < anthropic/claude < others
+        let provider_priority = [
+            ("gemini", 0),
+            ("openai", 1),
+            ("openrouter", 2),
+            ("anthropic", 3),
+            ("claude", 3),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+
+        let mut type_priority: HashMap<String, HashMap<String, i32>> = HashMap::new();
+        type_priority.insert(
+            "gemini".to_string(),
+            [
+                (TYPE_FLASH_LITE, 0),
+                (TYPE_FLASH, 1),
+                (TYPE_PRO, 2),
+                (TYPE_THINKING, 3),
+                (TYPE_GEMMA, 4),
+                (TYPE_STANDARD, 5),
+                ("other", 5),
+            ]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+        );
+        type_priority.insert(
+            "openai".to_string(),
+            [
+                (TYPE_MINI, 0),
+                (TYPE_O, 1),
+                (TYPE_45, 2),
+                (TYPE_4, 3),
+                (TYPE_35, 4),
+                ("other", 5),
+            ]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+        );
+        let claude_types: HashMap<String, i32> = [
+            (TYPE_SONNET, 0),
+            (TYPE_OPUS, 1),
+            (TYPE_HAIKU, 2),
+            ("other", 3),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+        type_priority.insert("anthropic".to_string(), claude_types.clone());
+        type_priority.insert("claude".to_string(), claude_types);
+
+        SortConfig {
+            provider_priority,
+            type_priority,
+            version_descending: true,
+            tie_break: TieBreakRule::NameAscending,
+        }
+    }
+}
+
+impl SortConfig {
+    fn provider_rank(&self, provider: &str) -> i32 {
+        *self.provider_priority.get(provider).unwrap_or(&100)
+    }
+
+    fn type_rank(&self, provider: &str, model_type: &str) -> i32 {
+        let Some(types) = self.type_priority.get(provider) else {
+            return 100;
+        };
+        *types.get(model_type).or_else(|| types.get("other")).unwrap_or(&100)
+    }
+}
+
+/// Sort models by provider, type (with special rules), version, then name
+pub fn sort_models(models_list: &mut [InternalModel], config: &SortConfig) {
     models_list.sort_by(|a, b| {
         // Normalize provider
         let pa = a.provider.to_lowercase();
         let pb = b.provider.to_lowercase();
-        let pr_a = *provider_priority.get(pa.as_str()).unwrap_or(&100);
-        let pr_b = *provider_priority.get(pb.as_str()).unwrap_or(&100);
+        let pr_a = config.provider_rank(&pa);
+        let pr_b = config.provider_rank(&pb);
         if pr_a != pr_b {
             return pr_a.cmp(&pr_b);
         }
@@ -195,6 +294,17 @@ pub fn sort_models(models_list: &mut [InternalModel]) {
         let ta = a.model_type.clone().unwrap_or_else(|| TYPE_STANDARD.to_string());
         let tb = b.model_type.clone().unwrap_or_else(|| TYPE_STANDARD.to_string());
 
+        let version_cmp = |a: &InternalModel, b: &InternalModel| -> Ordering {
+            let va = a.version.clone().unwrap_or_default()
+                .chars().filter(|c| c.is_digit(10) || *c == '.').collect::<String>()
+                .parse::<f64>().unwrap_or(0.0);
+            let vb = b.version.clone().unwrap_or_default()
+                .chars().filter(|c| c.is_digit(10) || *c == '.').collect::<String>()
+                .parse::<f64>().unwrap_or(0.0);
+            let cmp = va.partial_cmp(&vb).unwrap_or(Ordering::Equal);
+            if config.version_descending { cmp.reverse() } else { cmp }
+        };
+
         // Special: OpenAI mini series ordering
         if pa == "openai"
             && ta.to_lowercase() == TYPE_MINI.to_lowercase()
@@ -212,28 +322,18 @@ pub fn sort_models(models_list: &mut [InternalModel]) {
             let ma = mini_prio(&na);
             let mb = mini_prio(&nb);
             if ma != mb { return ma.cmp(&mb); }
-            // Compare version descending
-            let va = a.version.clone().unwrap_or_default()
-                .chars().filter(|c| c.is_digit(10) || *c == '.').collect::<String>()
-                .parse::<f64>().unwrap_or(0.0);
-            let vb = b.version.clone().unwrap_or_default()
-                .chars().filter(|c| c.is_digit(10) || *c == '.').collect::<String>()
-                .parse::<f64>().unwrap_or(0.0);
-            if (va - vb).abs() > f64::EPSILON {
-                return vb.partial_cmp(&va).unwrap_or(Ordering::Equal);
-            }
+            let vc = version_cmp(a, b);
+            if vc != Ordering::Equal { return vc; }
             return na.cmp(&nb);
         }
 
         // Provider-specific type priority
-        if pa == "gemini" {
-            let ra = *gemini_type_priority.get(ta.as_str()).unwrap_or(&gemini_type_priority[TYPE_STANDARD]);
-            let rb = *gemini_type_priority.get(tb.as_str()).unwrap_or(&gemini_type_priority[TYPE_STANDARD]);
-            if ra != rb { return ra.cmp(&rb); }
-        } else if pa == "openai" {
-            let ra = *openai_type_priority.get(ta.as_str()).unwrap_or(&openai_type_priority["other"]);
-            let rb = *openai_type_priority.get(tb.as_str()).unwrap_or(&openai_type_priority["other"]);
-            if ra != rb { return ra.cmp(&rb); }
+        let ra = config.type_rank(&pa, &ta);
+        let rb = config.type_rank(&pb, &tb);
+        if ra != rb {
+            return ra.cmp(&rb);
+        }
+        if pa == "openai" {
             // GPT-4 special ordering
             if ta == TYPE_4 && tb == TYPE_4 {
                 let na = a.name.clone().unwrap_or_else(|| a.id.clone()).to_lowercase();
@@ -246,35 +346,53 @@ pub fn sort_models(models_list: &mut [InternalModel]) {
                 if va != vb { return vb.cmp(&va); }
             }
             // "other" category shortest name first
-            if ra == openai_type_priority["other"] && rb == openai_type_priority["other"] {
+            if ra == config.type_rank("openai", "other") && rb == config.type_rank("openai", "other") {
                 let la = a.name.clone().unwrap_or_else(|| a.id.clone()).len();
                 let lb = b.name.clone().unwrap_or_else(|| b.id.clone()).len();
                 return la.cmp(&lb);
             }
-        } else if pa == "anthropic" || pa == "claude" {
-            let ra = *claude_type_priority.get(ta.as_str()).unwrap_or(&claude_type_priority["other"]);
-            let rb = *claude_type_priority.get(tb.as_str()).unwrap_or(&claude_type_priority["other"]);
-            if ra != rb { return ra.cmp(&rb); }
         }
 
-        // Version descending
-        let va = a.version.clone().unwrap_or_default()
-            .chars().filter(|c| c.is_digit(10) || *c == '.').collect::<String>()
-            .parse::<f64>().unwrap_or(0.0);
-        let vb = b.version.clone().unwrap_or_default()
-            .chars().filter(|c| c.is_digit(10) || *c == '.').collect::<String>()
-            .parse::<f64>().unwrap_or(0.0);
-        if (va - vb).abs() > f64::EPSILON {
-            return vb.partial_cmp(&va).unwrap_or(Ordering::Equal);
+        let vc = version_cmp(a, b);
+        if vc != Ordering::Equal {
+            return vc;
         }
 
-        // Final: name
-        let na = a.name.clone().unwrap_or_else(|| a.id.clone()).to_lowercase();
-        let nb = b.name.clone().unwrap_or_else(|| b.id.clone()).to_lowercase();
-        na.cmp(&nb)
+        // Final: tie-break (today, always name ascending)
+        match config.tie_break {
+            TieBreakRule::NameAscending => {
+                let na = a.name.clone().unwrap_or_else(|| a.id.clone()).to_lowercase();
+                let nb = b.name.clone().unwrap_or_else(|| b.id.clone()).to_lowercase();
+                na.cmp(&nb)
+            }
+        }
     });
 }
 
+/// Extracts the value(s) of `property` for a single model. Returns more than
+/// one value only for `capability`, where a model can belong to several
+/// groups at once; an unknown property yields no values.
+fn property_values(model: &InternalModel, property: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    match property {
+        "provider" => values.push(model.provider.clone()),
+        "family" => if let Some(f) = model.family.clone() { values.push(f) },
+        "type" => if let Some(t) = model.model_type.clone() { values.push(t) },
+        "series" => if let Some(s) = model.series.clone() { values.push(s) },
+        "variant" => if let Some(v) = model.variant.clone() { values.push(v) },
+        "capability" => {
+            for cap in &model.capabilities {
+                values.push(cap.clone());
+            }
+        }
+        "context_window" => values.push(categorize_context_window(model.context_size)),
+        "multimodal" => values.push(bool_to_yes_no(model.is_multimodal)),
+        "cost_tier" => values.push(categorize_cost_per_token(model.cost_per_token)),
+        _ => {}
+    }
+    values
+}
+
 /// Classify models by a given property into proto groups
 pub fn classify_models_by_property(
     models_list: &[InternalModel],
@@ -282,23 +400,7 @@ pub fn classify_models_by_property(
 ) -> Vec<ProtoClassifiedModelGroup> {
     let mut groups: HashMap<String, Vec<&InternalModel>> = HashMap::new();
     for model in models_list {
-        let mut values = Vec::new();
-        match property {
-            "provider" => values.push(model.provider.clone()),
-            "family" => if let Some(f) = model.family.clone() { values.push(f) },
-            "type" => if let Some(t) = model.model_type.clone() { values.push(t) },
-            "series" => if let Some(s) = model.series.clone() { values.push(s) },
-            "variant" => if let Some(v) = model.variant.clone() { values.push(v) },
-            "capability" => {
-                for cap in &model.capabilities {
-                    values.push(cap.clone());
-                }
-            }
-            "context_window" => values.push(categorize_context_window(model.context_size)),
-            "multimodal" => values.push(bool_to_yes_no(model.is_multimodal)),
-            _ => continue,
-        }
-        for val in values {
+        for val in property_values(model, property) {
             if !val.is_empty() {
                 groups.entry(val.clone()).or_default().push(model);
             }
@@ -318,4 +420,115 @@ pub fn classify_models_by_property(
         result.sort_by(|a, b| a.property_value.to_lowercase().cmp(&b.property_value.to_lowercase()));
     }
     result
-} 
\ No newline at end of file
+}
+
+/// Recursively partitions `models` by `properties[0]`, then `properties[1]`,
+/// etc., building a nested `HierarchicalModelGroup` tree with models placed
+/// at the leaf level. Reuses the same value-extraction logic as
+/// `classify_models_by_property`, so a model with several values for a
+/// property (only `capability` today) is fanned out into each matching branch.
+/// An empty `properties` slice has no group to build, so its caller's models
+/// end up with no representation — this only happens when the caller passes
+/// an empty property list, which `classify_models_with_criteria` does not do.
+pub fn build_hierarchical_groups(
+    models: &[InternalModel],
+    properties: &[String],
+) -> Vec<InternalHierarchicalModelGroup> {
+    let Some((property, rest)) = properties.split_first() else {
+        return Vec::new();
+    };
+    let mut groups: HashMap<String, Vec<InternalModel>> = HashMap::new();
+    for model in models {
+        for val in property_values(model, property) {
+            if !val.is_empty() {
+                groups.entry(val).or_default().push(model.clone());
+            }
+        }
+    }
+    let mut result: Vec<InternalHierarchicalModelGroup> = groups
+        .into_iter()
+        .map(|(value, models)| {
+            let children = build_hierarchical_groups(&models, rest);
+            InternalHierarchicalModelGroup {
+                group_name: property.clone(),
+                group_value: value,
+                models: if children.is_empty() { models } else { Vec::new() },
+                children,
+            }
+        })
+        .collect();
+    result.sort_by(|a, b| a.group_value.to_lowercase().cmp(&b.group_value.to_lowercase()));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(id: &str, provider: &str, model_type: &str, capabilities: &[&str]) -> InternalModel {
+        InternalModel {
+            id: id.to_string(),
+            name: None,
+            context_size: 0,
+            max_tokens: 0,
+            provider: provider.to_string(),
+            original_provider: None,
+            display_name: None,
+            description: None,
+            cost_per_token: 0.0,
+            capabilities: capabilities.iter().map(|c| c.to_string()).collect(),
+            family: None,
+            model_type: Some(model_type.to_string()),
+            series: None,
+            variant: None,
+            is_default: false,
+            is_multimodal: false,
+            is_experimental: false,
+            version: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn nests_by_each_property_in_order() {
+        let models = vec![
+            model("gpt-4o", "openai", "GPT 4", &["chat"]),
+            model("gpt-3.5-turbo", "openai", "GPT 3.5", &["chat"]),
+            model("claude-3-opus", "anthropic", "Opus", &["chat"]),
+        ];
+        let properties = vec!["provider".to_string(), "type".to_string()];
+
+        let tree = build_hierarchical_groups(&models, &properties);
+
+        let anthropic = tree.iter().find(|g| g.group_value == "anthropic").unwrap();
+        assert_eq!(anthropic.group_name, "provider");
+        assert_eq!(anthropic.children.len(), 1);
+        assert_eq!(anthropic.children[0].group_name, "type");
+        assert_eq!(anthropic.children[0].group_value, "Opus");
+        assert!(anthropic.children[0].children.is_empty());
+        assert_eq!(anthropic.children[0].models[0].id, "claude-3-opus");
+        // Intermediate levels hold no models directly, only at the leaf
+        assert!(anthropic.models.is_empty());
+
+        let openai = tree.iter().find(|g| g.group_value == "openai").unwrap();
+        assert_eq!(openai.children.len(), 2);
+    }
+
+    #[test]
+    fn fans_a_model_into_every_matching_capability_branch() {
+        let models = vec![model("gpt-4o", "openai", "GPT 4", &["vision", "function-calling"])];
+        let properties = vec!["capability".to_string()];
+
+        let tree = build_hierarchical_groups(&models, &properties);
+
+        assert_eq!(tree.len(), 2);
+        assert!(tree.iter().any(|g| g.group_value == "vision" && g.models[0].id == "gpt-4o"));
+        assert!(tree.iter().any(|g| g.group_value == "function-calling" && g.models[0].id == "gpt-4o"));
+    }
+
+    #[test]
+    fn empty_properties_yields_no_groups() {
+        let models = vec![model("gpt-4o", "openai", "GPT 4", &["chat"])];
+        assert!(build_hierarchical_groups(&models, &[]).is_empty());
+    }
+}
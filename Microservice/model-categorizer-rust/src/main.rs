@@ -3,8 +3,10 @@ use tonic_health::server::health_reporter;
 use tracing::info;
 use tracing_subscriber;
 
-mod handlers;
 mod classifiers;
+mod config;
+mod handlers;
+mod metrics;
 mod models;
 mod proto;
 use handlers::ModelClassificationHandler;
@@ -19,14 +21,21 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let port = std::env::var("PORT").unwrap_or_else(|_| "8090".to_string());
     let addr = format!("0.0.0.0:{}", port).parse()?;
 
+    // Read metrics port from environment or use default, then spawn the metrics server
+    // on its own listener so scraping never competes with the gRPC connection pool
+    let metrics_port = std::env::var("METRICS_PORT").unwrap_or_else(|_| "9090".to_string());
+    let metrics_addr = format!("0.0.0.0:{}", metrics_port).parse()?;
+    tokio::spawn(metrics::serve(metrics_addr));
+
     // Set up health reporter
     let (mut health_reporter, health_service) = tonic_health::server::health_reporter();
     health_reporter
         .set_serving::<ModelClassificationServiceServer<ModelClassificationHandler>>()
         .await;
 
-    // Create service handler
-    let classification_service = ModelClassificationServiceServer::new(ModelClassificationHandler::default());
+    // Create service handler, loading MODEL_CONFIG_FILE if set
+    let handler = ModelClassificationHandler::from_env()?;
+    let classification_service = ModelClassificationServiceServer::new(handler);
 
     tracing::info!("Model Classification Service starting on {}", addr);
 
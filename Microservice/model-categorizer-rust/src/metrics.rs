@@ -0,0 +1,182 @@
+use once_cell::sync::Lazy;
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, HistogramVec, IntCounterVec, Registry,
+    TextEncoder,
+};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use warp::Filter;
+
+/// RPC names used as the `rpc` label on all classification metrics
+pub const RPC_CLASSIFY_MODELS: &str = "classify_models";
+pub const RPC_CLASSIFY_MODELS_WITH_CRITERIA: &str = "classify_models_with_criteria";
+
+/// Global metrics registry for the classification service
+pub static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
+
+/// Total number of RPC calls received, labeled by `rpc`
+pub static RPC_CALLS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    let counter = register_int_counter_vec!(
+        "classify_rpc_calls_total",
+        "Total number of classification RPC calls received",
+        &["rpc"]
+    )
+    .expect("failed to register classify_rpc_calls_total");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("failed to register classify_rpc_calls_total with registry");
+    counter
+});
+
+/// Total number of RPC calls that completed successfully, labeled by `rpc`
+pub static RPC_SUCCESSES_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    let counter = register_int_counter_vec!(
+        "classify_rpc_successes_total",
+        "Total number of classification RPC calls that completed successfully",
+        &["rpc"]
+    )
+    .expect("failed to register classify_rpc_successes_total");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("failed to register classify_rpc_successes_total with registry");
+    counter
+});
+
+/// Total number of RPC calls that returned an error, labeled by `rpc`
+pub static RPC_ERRORS_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    let counter = register_int_counter_vec!(
+        "classify_rpc_errors_total",
+        "Total number of classification RPC calls that returned an error",
+        &["rpc"]
+    )
+    .expect("failed to register classify_rpc_errors_total");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("failed to register classify_rpc_errors_total with registry");
+    counter
+});
+
+/// RPC latency in seconds, labeled by `rpc`
+pub static RPC_LATENCY_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    let histogram = register_histogram_vec!(
+        "classify_rpc_latency_seconds",
+        "Classification RPC latency in seconds",
+        &["rpc"]
+    )
+    .expect("failed to register classify_rpc_latency_seconds");
+    REGISTRY
+        .register(Box::new(histogram.clone()))
+        .expect("failed to register classify_rpc_latency_seconds with registry");
+    histogram
+});
+
+/// Number of models received in a request, labeled by `rpc`
+pub static MODELS_RECEIVED_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    let counter = register_int_counter_vec!(
+        "classify_models_received_total",
+        "Total number of models received across classification requests",
+        &["rpc"]
+    )
+    .expect("failed to register classify_models_received_total");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("failed to register classify_models_received_total with registry");
+    counter
+});
+
+/// Number of hierarchical groups produced, labeled by `rpc`
+pub static GROUPS_PRODUCED_TOTAL: Lazy<IntCounterVec> = Lazy::new(|| {
+    let counter = register_int_counter_vec!(
+        "classify_groups_produced_total",
+        "Total number of hierarchical groups produced by classification requests",
+        &["rpc"]
+    )
+    .expect("failed to register classify_groups_produced_total");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("failed to register classify_groups_produced_total with registry");
+    counter
+});
+
+/// Number of models classified per provider, labeled by `provider`
+pub static MODELS_CLASSIFIED_BY_PROVIDER: Lazy<IntCounterVec> = Lazy::new(|| {
+    let counter = register_int_counter_vec!(
+        "classify_models_by_provider_total",
+        "Total number of models classified, labeled by resolved provider",
+        &["provider"]
+    )
+    .expect("failed to register classify_models_by_provider_total");
+    REGISTRY
+        .register(Box::new(counter.clone()))
+        .expect("failed to register classify_models_by_provider_total with registry");
+    counter
+});
+
+/// RAII guard that records call/success/error counts and latency for an RPC.
+///
+/// Create one at the top of a handler method and call [`RpcTimer::success`]
+/// or [`RpcTimer::error`] before returning; if neither is called (e.g. a
+/// panic unwinds through the handler) the drop impl records it as an error.
+pub struct RpcTimer {
+    rpc: &'static str,
+    start: std::time::Instant,
+    finished: bool,
+}
+
+impl RpcTimer {
+    /// Starts timing an RPC call and increments its call counter
+    pub fn start(rpc: &'static str) -> Self {
+        RPC_CALLS_TOTAL.with_label_values(&[rpc]).inc();
+        Self {
+            rpc,
+            start: std::time::Instant::now(),
+            finished: false,
+        }
+    }
+
+    /// Records a successful completion
+    pub fn success(mut self) {
+        RPC_SUCCESSES_TOTAL.with_label_values(&[self.rpc]).inc();
+        self.finish();
+    }
+
+    /// Records a failed completion
+    pub fn error(mut self) {
+        RPC_ERRORS_TOTAL.with_label_values(&[self.rpc]).inc();
+        self.finish();
+    }
+
+    fn finish(&mut self) {
+        if !self.finished {
+            RPC_LATENCY_SECONDS
+                .with_label_values(&[self.rpc])
+                .observe(self.start.elapsed().as_secs_f64());
+            self.finished = true;
+        }
+    }
+}
+
+impl Drop for RpcTimer {
+    fn drop(&mut self) {
+        if !self.finished {
+            RPC_ERRORS_TOTAL.with_label_values(&[self.rpc]).inc();
+            self.finish();
+        }
+    }
+}
+
+/// Renders the current registry in Prometheus text exposition format
+fn render() -> String {
+    let encoder = TextEncoder::new();
+    let metric_families = REGISTRY.gather();
+    encoder
+        .encode_to_string(&metric_families)
+        .unwrap_or_default()
+}
+
+/// Serves `/metrics` on a second HTTP listener alongside the gRPC server
+pub async fn serve(addr: SocketAddr) {
+    let route = warp::path("metrics").map(|| -> Result<_, Infallible> { Ok(render()) });
+    tracing::info!("Metrics server listening on {}", addr);
+    warp::serve(route).run(addr).await;
+}
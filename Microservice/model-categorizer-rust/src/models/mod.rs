@@ -1,7 +1,8 @@
+use rkyv::{Archive, Deserialize as RkyvDeserialize, Serialize as RkyvSerialize};
 use std::collections::HashMap;
 
 // Internal representation of a single LLM model
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Archive, RkyvSerialize, RkyvDeserialize)]
 pub struct Model {
     pub id: String,
     pub name: Option<String>,
@@ -69,7 +70,7 @@ pub struct ClassifiedModelResponse {
 }
 
 // Hierarchical grouping of models (nested)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Archive, RkyvSerialize, RkyvDeserialize)]
 pub struct HierarchicalModelGroup {
     pub group_name: String,
     pub group_value: String,
@@ -135,6 +136,17 @@ pub fn available_classification_properties() -> Vec<ClassificationProperty> {
             .map(str::to_string)
             .collect(),
         },
+        ClassificationProperty {
+            name: "cost_tier".to_string(),
+            display_name: Some("Cost Tier".to_string()),
+            description: Some("Grouping based on cost per token".to_string()),
+            possible_values: vec![
+                "Free", "Low (< $1/M)", "Medium ($1-10/M)", "High ($10-100/M)", "Premium (> $100/M)",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        },
     ];
     // Sort capabilities alphabetically for consistency
     if let Some(prop) = properties.iter_mut().find(|p| p.name == "capability") {
@@ -0,0 +1,3 @@
+pub mod modelservice {
+    tonic::include_proto!("modelservice");
+}